@@ -6,55 +6,260 @@ use std::collections::HashMap;
 pub enum Value {
     Null,
     Bool(bool),
-    Num(f64),
+    Num(Number),
     Str(String),
     Arr(Vec<Box<Value>>),
     Obj(HashMap<String, Box<Value>>),
 }
 
+/// A JSON number, keeping the integer vs. float distinction the source text
+/// carried so that 64-bit integers survive a round-trip intact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+}
+
+impl Number {
+    /// Returns the value as an `i64` when it fits, `None` for floats or
+    /// out-of-range unsigned values.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Number::Int(value) => Some(*value),
+            Number::Uint(value) => i64::try_from(*value).ok(),
+            Number::Float(_) => None,
+        }
+    }
+
+    /// Returns the value as a `u64` when it fits, `None` for floats or
+    /// negative values.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Number::Int(value) => u64::try_from(*value).ok(),
+            Number::Uint(value) => Some(*value),
+            Number::Float(_) => None,
+        }
+    }
+
+    /// Returns the value as an `f64`, widening any integer representation.
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Number::Int(value) => *value as f64,
+            Number::Uint(value) => *value as f64,
+            Number::Float(value) => *value,
+        }
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Number::Int(value) => write!(f, "{}", value),
+            Number::Uint(value) => write!(f, "{}", value),
+            Number::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
 // Serialization API
 
 pub fn serialize_value(value: &Value) -> String {
+    serialize_value_with(value, &mut CompactFormatter)
+}
+
+/// Produces a human-readable rendering indented by `indent` spaces per level.
+pub fn serialize_value_pretty(value: &Value, indent: usize) -> String {
+    serialize_value_with(value, &mut PrettyFormatter::new(indent))
+}
+
+/// Serializes `value` using `fmt` to decide how structural bytes and
+/// whitespace are emitted, keeping the data walk independent of the layout.
+pub fn serialize_value_with<F: Formatter>(value: &Value, fmt: &mut F) -> String {
+    let mut out = String::new();
+    write_value(value, fmt, &mut out);
+    out
+}
+
+fn write_value<F: Formatter>(value: &Value, fmt: &mut F, out: &mut String) {
     match value {
-        Value::Null => String::from("null"),
-        Value::Bool(bool_value) => bool_value.to_string(),
-        Value::Num(num_value) => num_value.to_string(),
-        Value::Str(str_value) => format!("\"{}\"", str_value),
-        Value::Arr(arr_value) => format!(
-            "[{}]",
-            arr_value
-                .iter()
-                .map(|boxed_value| serialize_value(&boxed_value))
-                .fold(String::new(), |mut concat_str, value_str| {
-                    if !concat_str.is_empty() {
-                        concat_str.push_str(",");
-                    }
+        Value::Null => out.push_str("null"),
+        Value::Bool(bool_value) => out.push_str(&bool_value.to_string()),
+        Value::Num(num_value) => out.push_str(&num_value.to_string()),
+        Value::Str(str_value) => out.push_str(&escape_string(str_value)),
+        Value::Arr(arr_value) => {
+            fmt.begin_array(out);
+            for (index, element) in arr_value.iter().enumerate() {
+                fmt.array_value_separator(out, index == 0);
+                write_value(element, fmt, out);
+            }
+            fmt.end_array(out, arr_value.is_empty());
+        }
+        Value::Obj(obj_value) => {
+            fmt.begin_object(out);
+            for (index, (key, element)) in obj_value.iter().enumerate() {
+                fmt.object_key(out, key, index == 0);
+                fmt.object_colon(out);
+                write_value(element, fmt, out);
+            }
+            fmt.end_object(out, obj_value.is_empty());
+        }
+    }
+}
 
-                    concat_str.push_str(&value_str);
-                    concat_str
-                })
-        ),
-        Value::Obj(obj_value) => format!(
-            "{{{}}}",
-            obj_value
-                .iter()
-                .map(|boxed_value| (boxed_value.0, serialize_value(&boxed_value.1)))
-                .fold(String::new(), |mut concat_str, value_tuple| {
-                    if !concat_str.is_empty() {
-                        concat_str.push_str(",");
-                    }
+/// Decides how the structural characters and surrounding whitespace of a
+/// serialized document are laid out, independent of the value walk itself.
+pub trait Formatter {
+    fn begin_array(&mut self, out: &mut String);
+    fn array_value_separator(&mut self, out: &mut String, first: bool);
+    fn end_array(&mut self, out: &mut String, empty: bool);
+    fn begin_object(&mut self, out: &mut String);
+    fn object_key(&mut self, out: &mut String, key: &str, first: bool);
+    fn object_colon(&mut self, out: &mut String);
+    fn end_object(&mut self, out: &mut String, empty: bool);
+}
+
+/// Emits the most compact representation, matching the crate's original output.
+pub struct CompactFormatter;
+
+impl Formatter for CompactFormatter {
+    fn begin_array(&mut self, out: &mut String) {
+        out.push('[');
+    }
+
+    fn array_value_separator(&mut self, out: &mut String, first: bool) {
+        if !first {
+            out.push(',');
+        }
+    }
+
+    fn end_array(&mut self, out: &mut String, _empty: bool) {
+        out.push(']');
+    }
+
+    fn begin_object(&mut self, out: &mut String) {
+        out.push('{');
+    }
+
+    fn object_key(&mut self, out: &mut String, key: &str, first: bool) {
+        if !first {
+            out.push(',');
+        }
+        out.push_str(&escape_string(key));
+    }
+
+    fn object_colon(&mut self, out: &mut String) {
+        out.push_str(": ");
+    }
+
+    fn end_object(&mut self, out: &mut String, _empty: bool) {
+        out.push('}');
+    }
+}
+
+/// Emits newline-separated, `indent`-space-nested output for human readers.
+pub struct PrettyFormatter {
+    pub indent: usize,
+    depth: usize,
+}
 
-                    concat_str.push_str(&format!("\"{}\"", value_tuple.0));
-                    concat_str.push_str(": ");
-                    concat_str.push_str(&value_tuple.1);
-                    concat_str
-                })
-        ),
+impl PrettyFormatter {
+    pub fn new(indent: usize) -> PrettyFormatter {
+        PrettyFormatter { indent, depth: 0 }
+    }
+
+    fn write_newline_indent(&self, out: &mut String) {
+        out.push('\n');
+        for _ in 0..self.depth * self.indent {
+            out.push(' ');
+        }
     }
 }
 
+impl Formatter for PrettyFormatter {
+    fn begin_array(&mut self, out: &mut String) {
+        out.push('[');
+        self.depth += 1;
+    }
+
+    fn array_value_separator(&mut self, out: &mut String, first: bool) {
+        if !first {
+            out.push(',');
+        }
+        self.write_newline_indent(out);
+    }
+
+    fn end_array(&mut self, out: &mut String, empty: bool) {
+        self.depth -= 1;
+        if !empty {
+            self.write_newline_indent(out);
+        }
+        out.push(']');
+    }
+
+    fn begin_object(&mut self, out: &mut String) {
+        out.push('{');
+        self.depth += 1;
+    }
+
+    fn object_key(&mut self, out: &mut String, key: &str, first: bool) {
+        if !first {
+            out.push(',');
+        }
+        self.write_newline_indent(out);
+        out.push_str(&escape_string(key));
+    }
+
+    fn object_colon(&mut self, out: &mut String) {
+        out.push_str(": ");
+    }
+
+    fn end_object(&mut self, out: &mut String, empty: bool) {
+        self.depth -= 1;
+        if !empty {
+            self.write_newline_indent(out);
+        }
+        out.push('}');
+    }
+}
+
+fn escape_string(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\u{08}' => result.push_str("\\b"),
+            '\u{0c}' => result.push_str("\\f"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
 // Deserialization API
 
+/// A parsing failure, carrying both a human-readable message and the source
+/// line it was detected on so callers can point at the offending input.
+/// Errors from a JSONPath expression (see `select`) have no source line and
+/// carry line 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[Line {}] Error: {}", self.line, self.message)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 enum TokenKind {
     // Single-character tokens.
@@ -67,7 +272,7 @@ enum TokenKind {
 
     // Literals
     Str(String),
-    Num(String),
+    Num(Number),
     True,
     False,
     Null,
@@ -88,7 +293,7 @@ struct Scanner<'a> {
     start: usize,
     current: usize,
     line: usize,
-    errors: Vec<String>,
+    errors: Vec<ParseError>,
 }
 
 impl<'a> Scanner<'a> {
@@ -103,13 +308,33 @@ impl<'a> Scanner<'a> {
         }
     }
 
-    fn scan_tokens(&mut self) {
-        while !self.is_at_end() {
+    /// Scans and returns the next token, skipping insignificant whitespace.
+    /// Unlike a full pre-scan this produces tokens one at a time so callers can
+    /// drive the lexer incrementally. The `tokens` vector is used only as
+    /// scratch space to reuse `scan_token`.
+    fn next_token(&mut self) -> Result<Token, ParseError> {
+        loop {
+            if self.is_at_end() {
+                return Ok(Token {
+                    kind: TokenKind::Eof,
+                    line: self.line,
+                });
+            }
+
             self.start = self.current;
+            let tokens_before = self.tokens.len();
             self.scan_token();
-        }
 
-        self.add_token(TokenKind::Eof);
+            if let Some(error) = self.errors.last() {
+                return Err(error.clone());
+            }
+
+            if self.tokens.len() > tokens_before {
+                return Ok(self.tokens.pop().unwrap());
+            }
+
+            // The consumed character was insignificant whitespace; keep going.
+        }
     }
 
     fn scan_token(&mut self) {
@@ -171,25 +396,114 @@ impl<'a> Scanner<'a> {
     }
 
     fn add_string_token(&mut self) {
-        while !self.is_at_end() {
-            match self.peek() {
-                '\n' => self.line += 1,
-                '"' => break,
-                _ => (),
+        // Decode the string contents byte-by-byte so that escape sequences are
+        // translated into their real values while multi-byte UTF-8 characters
+        // are copied through untouched.
+        let mut bytes = Vec::<u8>::new();
+        loop {
+            if self.is_at_end() {
+                self.report_error("Unterminated string!");
+                return;
             }
 
-            self.advance();
+            let c = self.advance();
+            match c {
+                '"' => break,
+                '\n' => {
+                    self.line += 1;
+                    bytes.push(b'\n');
+                }
+                '\\' => {
+                    if !self.scan_escape(&mut bytes) {
+                        return;
+                    }
+                }
+                _ => bytes.push(self.buffer[self.current - 1]),
+            }
         }
 
+        self.add_token(TokenKind::Str(String::from_utf8(bytes).unwrap()));
+    }
+
+    fn scan_escape(&mut self, bytes: &mut Vec<u8>) -> bool {
         if self.is_at_end() {
             self.report_error("Unterminated string!");
-            return;
+            return false;
         }
 
-        self.advance();
-        self.add_token(TokenKind::Str(
-            String::from_utf8(self.buffer[self.start + 1..self.current - 1].to_vec()).unwrap(),
-        ));
+        let c = self.advance();
+        match c {
+            '"' => bytes.push(b'"'),
+            '\\' => bytes.push(b'\\'),
+            '/' => bytes.push(b'/'),
+            'b' => bytes.push(0x08),
+            'f' => bytes.push(0x0c),
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            'u' => match self.scan_unicode_escape() {
+                Some(decoded) => {
+                    let mut buffer = [0u8; 4];
+                    bytes.extend_from_slice(decoded.encode_utf8(&mut buffer).as_bytes());
+                }
+                None => return false,
+            },
+            _ => {
+                self.report_error("Invalid escape sequence!");
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn scan_unicode_escape(&mut self) -> Option<char> {
+        let hi = self.scan_hex4()?;
+
+        // A high surrogate must be followed by a low surrogate and combined
+        // into a single supplementary-plane code point.
+        if (0xD800..=0xDBFF).contains(&hi) {
+            if !self.try_match('\\') || !self.try_match('u') {
+                self.report_error("Unpaired Unicode surrogate!");
+                return None;
+            }
+
+            let lo = self.scan_hex4()?;
+            if !(0xDC00..=0xDFFF).contains(&lo) {
+                self.report_error("Unpaired Unicode surrogate!");
+                return None;
+            }
+
+            let code_point = 0x10000 + (((hi as u32) - 0xD800) << 10) + ((lo as u32) - 0xDC00);
+            return char::from_u32(code_point);
+        }
+
+        if (0xDC00..=0xDFFF).contains(&hi) {
+            self.report_error("Unpaired Unicode surrogate!");
+            return None;
+        }
+
+        char::from_u32(hi as u32)
+    }
+
+    fn scan_hex4(&mut self) -> Option<u16> {
+        let mut result: u16 = 0;
+        for _ in 0..4 {
+            if self.is_at_end() {
+                self.report_error("Unterminated Unicode escape sequence!");
+                return None;
+            }
+
+            match self.advance().to_digit(16) {
+                Some(digit) => result = result * 16 + digit as u16,
+                None => {
+                    self.report_error("Invalid Unicode escape sequence!");
+                    return None;
+                }
+            }
+        }
+
+        Some(result)
     }
 
     fn add_number_token(&mut self, first_c: char) {
@@ -210,8 +524,13 @@ impl<'a> Scanner<'a> {
             }
         }
 
+        // Whether the literal has a fraction or exponent part, which decides
+        // between an integer and a floating-point representation.
+        let mut is_float = false;
+
         // Parse the fraction part.
         if self.peek() == '.' {
+            is_float = true;
             self.advance();
 
             let mut has_fraction_digit = false;
@@ -228,6 +547,7 @@ impl<'a> Scanner<'a> {
         // Parse the exponent part.
         match self.peek() {
             'e' | 'E' => {
+                is_float = true;
                 self.advance();
                 match self.peek() {
                     '+' | '-' => {
@@ -250,9 +570,25 @@ impl<'a> Scanner<'a> {
             _ => (),
         }
 
-        self.add_token(TokenKind::Num(
-            String::from_utf8(self.buffer[self.start..self.current].to_vec()).unwrap(),
-        ));
+        let literal = std::str::from_utf8(&self.buffer[self.start..self.current]).unwrap();
+        let number = if is_float {
+            Number::Float(literal.parse::<f64>().unwrap())
+        } else if literal.starts_with('-') {
+            // A signed integer literal; fall back to a float if it overflows i64.
+            literal
+                .parse::<i64>()
+                .map(Number::Int)
+                .unwrap_or_else(|_| Number::Float(literal.parse::<f64>().unwrap()))
+        } else {
+            // A non-negative integer literal; widen to u64, then to f64 as needed.
+            literal
+                .parse::<i64>()
+                .map(Number::Int)
+                .or_else(|_| literal.parse::<u64>().map(Number::Uint))
+                .unwrap_or_else(|_| Number::Float(literal.parse::<f64>().unwrap()))
+        };
+
+        self.add_token(TokenKind::Num(number));
     }
 
     fn try_match_str(&mut self, expected: &str) -> bool {
@@ -288,282 +624,795 @@ impl<'a> Scanner<'a> {
     }
 
     fn report_error(&mut self, message: &str) {
-        self.errors
-            .push(format!("[Line {}] Error: {}", self.line, message));
+        self.errors.push(ParseError {
+            message: message.to_string(),
+            line: self.line,
+        });
     }
 }
 
-struct Parser<'a> {
-    tokens: &'a Vec<Token>,
-    current: usize,
-    errors: Vec<String>,
+/// One step of the flat event sequence yielded by `EventReader::next_event`.
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    StartObject,
+    Key(String),
+    StartArray,
+    EndArray,
+    EndObject,
+    Str(String),
+    Num(Number),
+    Bool(bool),
+    Null,
+    Eof,
 }
 
-impl<'a> Parser<'a> {
-    fn new(tokens: &'a Vec<Token>) -> Parser {
-        Parser {
-            tokens: tokens,
-            current: 0,
-            errors: Vec::new(),
+enum Frame {
+    Array { has_items: bool },
+    Object { has_items: bool },
+}
+
+/// A pull (event) parser that drives the `Scanner` incrementally and yields a
+/// flat sequence of `Event`s, letting callers filter or extract fields without
+/// materializing the whole `Value` tree.
+pub struct EventReader<'a> {
+    scanner: Scanner<'a>,
+    stack: Vec<Frame>,
+    pending_object_value: bool,
+    done: bool,
+}
+
+impl<'a> EventReader<'a> {
+    pub fn new(buffer: &'a str) -> EventReader<'a> {
+        EventReader {
+            scanner: Scanner::new(buffer),
+            stack: Vec::new(),
+            pending_object_value: false,
+            done: false,
         }
     }
 
-    fn parse_tokens(&mut self) -> Option<Value> {
-        let maybe_value = self.parse_value();
+    pub fn next_event(&mut self) -> Result<Event, ParseError> {
+        // Once the top-level value is consumed only trailing whitespace (and a
+        // final `Eof`) may follow; anything else is an error.
+        if self.done {
+            let token = self.scanner.next_token()?;
+            if token.kind != TokenKind::Eof {
+                return Err(error_at(&token, "Expected EOF!"));
+            }
+            return Ok(Event::Eof);
+        }
 
-        if self.is_at_end() || self.peek().kind != TokenKind::Eof {
-            self.report_error("Expected EOF!");
-            return None;
+        match self.stack.last() {
+            None => {
+                let token = self.scanner.next_token()?;
+                let event = self.begin_value(token)?;
+                if self.stack.is_empty() {
+                    // A scalar top-level value completes the document.
+                    self.done = true;
+                }
+                Ok(event)
+            }
+            Some(Frame::Array { .. }) => self.next_in_array(),
+            Some(Frame::Object { .. }) => self.next_in_object(),
         }
+    }
 
-        return maybe_value;
+    fn begin_value(&mut self, token: Token) -> Result<Event, ParseError> {
+        match token.kind {
+            TokenKind::Str(str_value) => Ok(Event::Str(str_value)),
+            TokenKind::Num(number) => Ok(Event::Num(number)),
+            TokenKind::True => Ok(Event::Bool(true)),
+            TokenKind::False => Ok(Event::Bool(false)),
+            TokenKind::Null => Ok(Event::Null),
+            TokenKind::LeftSquaredBrace => {
+                self.stack.push(Frame::Array { has_items: false });
+                Ok(Event::StartArray)
+            }
+            TokenKind::LeftCurlyBrace => {
+                self.stack.push(Frame::Object { has_items: false });
+                Ok(Event::StartObject)
+            }
+            _ => Err(error_at(&token, "Unexpected token!")),
+        }
     }
 
-    fn parse_value(&mut self) -> Option<Value> {
-        if self.is_at_end() {
-            self.report_error("Unexpected EOF!");
-            return None;
+    fn next_in_array(&mut self) -> Result<Event, ParseError> {
+        let token = self.scanner.next_token()?;
+        if token.kind == TokenKind::RightSquaredBrace {
+            self.stack.pop();
+            self.finish_frame();
+            return Ok(Event::EndArray);
         }
 
-        let first_token = self.peek();
-        match &first_token.kind {
-            TokenKind::Str(str_value) => {
-                self.advance();
-                return Some(Value::Str(str_value.clone()));
+        let has_items = matches!(self.stack.last(), Some(Frame::Array { has_items: true }));
+        let value_token = if has_items {
+            if token.kind != TokenKind::Comma {
+                return Err(error_at(
+                    &token,
+                    "JSON array elements must be separated by a comma!",
+                ));
             }
+            self.scanner.next_token()?
+        } else {
+            token
+        };
 
-            TokenKind::Num(str_value) => {
-                self.advance();
-                return Some(Value::Num(str_value.parse::<f64>().unwrap()));
+        if let Some(Frame::Array { has_items }) = self.stack.last_mut() {
+            *has_items = true;
+        }
+        self.begin_value(value_token)
+    }
+
+    fn next_in_object(&mut self) -> Result<Event, ParseError> {
+        // After a `Key` event the following call yields that member's value.
+        if self.pending_object_value {
+            self.pending_object_value = false;
+            let token = self.scanner.next_token()?;
+            return self.begin_value(token);
+        }
+
+        let token = self.scanner.next_token()?;
+        if token.kind == TokenKind::RightCurlyBrace {
+            self.stack.pop();
+            self.finish_frame();
+            return Ok(Event::EndObject);
+        }
+
+        let has_items = matches!(self.stack.last(), Some(Frame::Object { has_items: true }));
+        let key_token = if has_items {
+            if token.kind != TokenKind::Comma {
+                return Err(error_at(
+                    &token,
+                    "JSON object members must be separated by a comma!",
+                ));
             }
+            self.scanner.next_token()?
+        } else {
+            token
+        };
+
+        let key = match key_token.kind {
+            TokenKind::Str(str_value) => str_value,
+            _ => return Err(error_at(&key_token, "Expected JSON object element key!")),
+        };
+
+        let colon = self.scanner.next_token()?;
+        if colon.kind != TokenKind::Colon {
+            return Err(error_at(
+                &colon,
+                "Expected JSON object element key-value colon separator!",
+            ));
+        }
 
-            TokenKind::True => {
-                self.advance();
-                return Some(Value::Bool(true));
+        if let Some(Frame::Object { has_items }) = self.stack.last_mut() {
+            *has_items = true;
+        }
+        self.pending_object_value = true;
+        Ok(Event::Key(key))
+    }
+
+    fn finish_frame(&mut self) {
+        if self.stack.is_empty() {
+            self.done = true;
+        }
+    }
+}
+
+fn error_at(token: &Token, message: &str) -> ParseError {
+    ParseError {
+        message: message.to_string(),
+        line: token.line,
+    }
+}
+
+pub fn deserialize_value(buffer: &str) -> Result<Value, Vec<ParseError>> {
+    let mut reader = EventReader::new(buffer);
+
+    let value = match read_value(&mut reader) {
+        Ok(value) => value,
+        Err(error) => return Err(vec![error]),
+    };
+
+    match reader.next_event() {
+        Ok(Event::Eof) => Ok(value),
+        Ok(_) => Err(vec![ParseError {
+            message: String::from("Expected EOF!"),
+            line: 0,
+        }]),
+        Err(error) => Err(vec![error]),
+    }
+}
+
+fn read_value(reader: &mut EventReader) -> Result<Value, ParseError> {
+    let event = reader.next_event()?;
+    build_value(reader, event)
+}
+
+fn build_value(reader: &mut EventReader, event: Event) -> Result<Value, ParseError> {
+    match event {
+        Event::Null => Ok(Value::Null),
+        Event::Bool(bool_value) => Ok(Value::Bool(bool_value)),
+        Event::Num(number) => Ok(Value::Num(number)),
+        Event::Str(str_value) => Ok(Value::Str(str_value)),
+
+        Event::StartArray => {
+            let mut result_arr = Vec::<Box<Value>>::new();
+            loop {
+                let event = reader.next_event()?;
+                if event == Event::EndArray {
+                    return Ok(Value::Arr(result_arr));
+                }
+
+                result_arr.push(Box::new(build_value(reader, event)?));
             }
+        }
 
-            TokenKind::False => {
-                self.advance();
-                return Some(Value::Bool(false));
+        Event::StartObject => {
+            let mut result_obj = HashMap::<String, Box<Value>>::new();
+            loop {
+                let event = reader.next_event()?;
+                match event {
+                    Event::EndObject => return Ok(Value::Obj(result_obj)),
+                    Event::Key(key) => {
+                        let value = read_value(reader)?;
+                        if result_obj.contains_key(&key) {
+                            return Err(ParseError {
+                                message: format!("Duplicate JSON object key! Key {}", &key),
+                                line: reader.scanner.line,
+                            });
+                        }
+
+                        result_obj.insert(key, Box::new(value));
+                    }
+                    _ => {
+                        return Err(ParseError {
+                            message: String::from("Expected JSON object element key!"),
+                            line: 0,
+                        })
+                    }
+                }
             }
+        }
 
-            TokenKind::Null => {
-                self.advance();
-                return Some(Value::Null);
+        Event::Eof => Err(ParseError {
+            message: String::from("Unexpected EOF!"),
+            line: 0,
+        }),
+
+        _ => Err(ParseError {
+            message: String::from("Unexpected token!"),
+            line: 0,
+        }),
+    }
+}
+/// Convenience wrapper that discards the structured errors, mirroring the
+/// previous `Option`-returning API for callers that only care about success.
+pub fn deserialize_value_ok(buffer: &str) -> Option<Value> {
+    deserialize_value(buffer).ok()
+}
+
+// JSONPath API
+
+#[derive(Debug, PartialEq)]
+enum Selector {
+    Root,
+    Key(String),
+    Index(i64),
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    Wildcard,
+    RecursiveDescent,
+}
+
+/// Evaluates a JSONPath expression against an already-parsed `Value` tree,
+/// returning references to every node the path matches.
+pub fn select<'a>(root: &'a Value, path: &str) -> Result<Vec<&'a Value>, ParseError> {
+    let selectors = parse_path(path)?;
+
+    let mut current: Vec<&'a Value> = vec![root];
+    for selector in &selectors {
+        let mut next: Vec<&'a Value> = Vec::new();
+        match selector {
+            Selector::Root => next.extend(current.iter().copied()),
+
+            Selector::Key(key) => {
+                for node in &current {
+                    if let Value::Obj(obj_value) = *node {
+                        if let Some(child) = obj_value.get(key) {
+                            next.push(child.as_ref());
+                        }
+                    }
+                }
             }
 
-            TokenKind::LeftCurlyBrace => {
-                return self.parse_object();
+            Selector::Index(index) => {
+                for node in &current {
+                    if let Value::Arr(arr_value) = *node {
+                        if let Some(resolved) = resolve_index(*index, arr_value.len()) {
+                            next.push(arr_value[resolved].as_ref());
+                        }
+                    }
+                }
             }
 
-            TokenKind::LeftSquaredBrace => {
-                return self.parse_array();
+            Selector::Slice { start, end, step } => {
+                for node in &current {
+                    if let Value::Arr(arr_value) = *node {
+                        apply_slice(arr_value, *start, *end, *step, &mut next);
+                    }
+                }
             }
 
-            _ => {
-                self.report_error_with_token(first_token, "Unexpected token!");
-                return None;
+            Selector::Wildcard => {
+                for node in &current {
+                    match *node {
+                        Value::Obj(obj_value) => {
+                            next.extend(obj_value.values().map(|child| child.as_ref()))
+                        }
+                        Value::Arr(arr_value) => {
+                            next.extend(arr_value.iter().map(|child| child.as_ref()))
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            Selector::RecursiveDescent => {
+                for node in &current {
+                    collect_descendants(node, &mut next);
+                }
             }
         }
+
+        current = next;
     }
 
-    fn parse_object(&mut self) -> Option<Value> {
-        if self.is_at_end() {
-            self.report_error("Unexpected EOF!");
-            return None;
-        }
+    Ok(current)
+}
 
-        if !self.try_match(TokenKind::LeftCurlyBrace) {
-            self.report_error_with_token(self.peek(), "Expected left curly brace!");
-            return None;
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { len as i64 + index } else { index };
+    if resolved < 0 || resolved >= len as i64 {
+        None
+    } else {
+        Some(resolved as usize)
+    }
+}
+
+fn apply_slice<'a>(
+    arr_value: &'a [Box<Value>],
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+    out: &mut Vec<&'a Value>,
+) {
+    let len = arr_value.len() as i64;
+    let step = step.unwrap_or(1);
+    if step == 0 {
+        return;
+    }
+
+    let normalize = |value: i64| if value < 0 { len + value } else { value };
+
+    if step > 0 {
+        let mut index = start.map(|value| normalize(value).max(0)).unwrap_or(0);
+        let stop = end.map(|value| normalize(value).min(len)).unwrap_or(len);
+        while index < stop {
+            out.push(arr_value[index as usize].as_ref());
+            index += step;
         }
+    } else {
+        let mut index = start
+            .map(|value| normalize(value).min(len - 1))
+            .unwrap_or(len - 1);
+        let stop = end.map(normalize).unwrap_or(-1);
+        while index > stop && index >= 0 {
+            out.push(arr_value[index as usize].as_ref());
+            index += step;
+        }
+    }
+}
 
-        let mut result_obj = HashMap::<String, Box<Value>>::new();
-        let mut previous_match_is_member = false;
-        loop {
-            if self.is_at_end() {
-                self.report_error("Unexpected EOF!");
-                return None;
+fn collect_descendants<'a>(node: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(node);
+    match node {
+        Value::Arr(arr_value) => {
+            for child in arr_value {
+                collect_descendants(child.as_ref(), out);
             }
-
-            if self.try_match(TokenKind::RightCurlyBrace) {
-                return Some(Value::Obj(result_obj));
+        }
+        Value::Obj(obj_value) => {
+            for child in obj_value.values() {
+                collect_descendants(child.as_ref(), out);
             }
+        }
+        _ => (),
+    }
+}
 
-            let next_token = self.peek();
-            if previous_match_is_member {
-                if !self.try_match(TokenKind::Comma) {
-                    self.report_error_with_token(
-                        next_token,
-                        "JSON object members must be separated by a comma!",
-                    );
-                    return None;
+fn path_error(message: &str) -> ParseError {
+    ParseError {
+        message: message.to_string(),
+        line: 0,
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<Selector>, ParseError> {
+    let bytes = path.as_bytes();
+    if bytes.first() != Some(&b'$') {
+        return Err(path_error("JSONPath must start with '$'!"));
+    }
+
+    let mut selectors = vec![Selector::Root];
+    let mut pos = 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'.' => {
+                pos += 1;
+                if pos < bytes.len() && bytes[pos] == b'.' {
+                    pos += 1;
+                    selectors.push(Selector::RecursiveDescent);
+
+                    // `..name` / `..*` attach a child selector directly; `..[`
+                    // and a trailing `..` are handled by the outer loop.
+                    if pos < bytes.len() && (is_name_char(bytes[pos]) || bytes[pos] == b'*') {
+                        selectors.push(read_dot_child(bytes, &mut pos)?);
+                    }
+                } else {
+                    selectors.push(read_dot_child(bytes, &mut pos)?);
                 }
             }
 
-            let maybe_member = self.parse_object_member();
-            if maybe_member.is_none() {
-                self.report_error_with_token(next_token, "Failed to parse JSON object member!");
-                return None;
-            }
+            b'[' => selectors.push(read_bracket(bytes, &mut pos)?),
 
-            let member = maybe_member.unwrap();
-            if result_obj.contains_key(&member.0) {
-                self.report_error_with_token(
-                    next_token,
-                    &format!("Duplicate JSON object key! Key {}", &member.0),
-                );
-                return None;
-            }
+            _ => return Err(path_error("Unexpected character in JSONPath!")),
+        }
+    }
+
+    Ok(selectors)
+}
+
+fn read_dot_child(bytes: &[u8], pos: &mut usize) -> Result<Selector, ParseError> {
+    if *pos < bytes.len() && bytes[*pos] == b'*' {
+        *pos += 1;
+        return Ok(Selector::Wildcard);
+    }
+
+    let start = *pos;
+    while *pos < bytes.len() && is_name_char(bytes[*pos]) {
+        *pos += 1;
+    }
 
-            result_obj.insert(member.0, Box::new(member.1));
-            previous_match_is_member = true;
+    if *pos == start {
+        return Err(path_error("Expected a member name after '.'!"));
+    }
+
+    Ok(Selector::Key(
+        String::from_utf8(bytes[start..*pos].to_vec()).unwrap(),
+    ))
+}
+
+fn read_bracket(bytes: &[u8], pos: &mut usize) -> Result<Selector, ParseError> {
+    *pos += 1;
+    if *pos >= bytes.len() {
+        return Err(path_error("Unterminated '[' in JSONPath!"));
+    }
+
+    let selector = match bytes[*pos] {
+        b'*' => {
+            *pos += 1;
+            Selector::Wildcard
         }
+        b'"' | b'\'' => read_quoted_key(bytes, pos)?,
+        _ => read_index_or_slice(bytes, pos)?,
+    };
+
+    if *pos >= bytes.len() || bytes[*pos] != b']' {
+        return Err(path_error("Expected ']' in JSONPath!"));
     }
+    *pos += 1;
 
-    fn parse_object_member(&mut self) -> Option<(String, Value)> {
-        if self.is_at_end() {
-            self.report_error("Unexpected EOF!");
-            return None;
+    Ok(selector)
+}
+
+fn read_quoted_key(bytes: &[u8], pos: &mut usize) -> Result<Selector, ParseError> {
+    let quote = bytes[*pos];
+    *pos += 1;
+
+    let start = *pos;
+    while *pos < bytes.len() && bytes[*pos] != quote {
+        *pos += 1;
+    }
+
+    if *pos >= bytes.len() {
+        return Err(path_error("Unterminated quoted key in JSONPath!"));
+    }
+
+    let key = String::from_utf8(bytes[start..*pos].to_vec()).unwrap();
+    *pos += 1;
+
+    Ok(Selector::Key(key))
+}
+
+fn read_index_or_slice(bytes: &[u8], pos: &mut usize) -> Result<Selector, ParseError> {
+    let start = *pos;
+    while *pos < bytes.len() && bytes[*pos] != b']' {
+        *pos += 1;
+    }
+
+    let content = std::str::from_utf8(&bytes[start..*pos]).unwrap();
+    if !content.contains(':') {
+        let index = content
+            .parse::<i64>()
+            .map_err(|_| path_error("Invalid array index in JSONPath!"))?;
+        return Ok(Selector::Index(index));
+    }
+
+    let parts: Vec<&str> = content.split(':').collect();
+    if parts.len() > 3 {
+        return Err(path_error("Invalid array slice in JSONPath!"));
+    }
+
+    let parse_bound = |part: &str| -> Result<Option<i64>, ParseError> {
+        if part.is_empty() {
+            Ok(None)
+        } else {
+            part.parse::<i64>()
+                .map(Some)
+                .map_err(|_| path_error("Invalid array slice bound in JSONPath!"))
         }
+    };
+
+    Ok(Selector::Slice {
+        start: parse_bound(parts[0])?,
+        end: parse_bound(parts[1])?,
+        step: if parts.len() == 3 {
+            parse_bound(parts[2])?
+        } else {
+            None
+        },
+    })
+}
 
-        let result_key;
-        let result_value;
+fn is_name_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
 
-        let next_token = self.peek();
-        match &next_token.kind {
-            TokenKind::Str(str_value) => {
-                result_key = str_value.clone();
-                self.advance();
-            }
+// Typed encoding / decoding API
 
-            _ => {
-                self.report_error_with_token(next_token, "Expected JSON object element key!");
-                return None;
-            }
+/// A failure while mapping a `Value` onto a Rust type: a missing key or a
+/// type mismatch. The `path` is always `$`, the root of the value being
+/// decoded; the `FromJson` impls for `Vec`, `HashMap` and `Option` do not
+/// extend it with the index or key of a nested element that failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    MissingKey { path: String, key: String },
+    TypeMismatch { path: String, expected: String },
+}
+
+impl DecodeError {
+    pub fn missing_key(key: &str) -> DecodeError {
+        DecodeError::MissingKey {
+            path: String::from("$"),
+            key: key.to_string(),
         }
+    }
 
-        if self.is_at_end() {
-            self.report_error("Unexpected EOF!");
-            return None;
+    pub fn type_mismatch(expected: &str) -> DecodeError {
+        DecodeError::TypeMismatch {
+            path: String::from("$"),
+            expected: expected.to_string(),
         }
+    }
+}
 
-        if !self.try_match(TokenKind::Colon) {
-            self.report_error_with_token(
-                self.peek(),
-                "Expected JSON object element key-value colon separator!",
-            );
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DecodeError::MissingKey { path, key } => {
+                write!(f, "{}: missing key '{}'", path, key)
+            }
+            DecodeError::TypeMismatch { path, expected } => {
+                write!(f, "{}: expected {}", path, expected)
+            }
         }
+    }
+}
 
-        result_value = self.parse_value();
-        if result_value.is_none() {
-            self.report_error("Failed to parse JSON object element value!");
-            return None;
+impl Value {
+    /// Returns the child stored under `key` when this is an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Obj(obj_value) => obj_value.get(key).map(|child| child.as_ref()),
+            _ => None,
         }
+    }
 
-        Some((result_key, result_value.unwrap()))
+    /// Returns the string contents when this is a `Value::Str`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(str_value) => Some(str_value),
+            _ => None,
+        }
     }
 
-    fn parse_array(&mut self) -> Option<Value> {
-        if self.is_at_end() {
-            self.report_error("Unexpected EOF!");
-            return None;
+    /// Returns the elements when this is a `Value::Arr`.
+    pub fn as_array(&self) -> Option<&Vec<Box<Value>>> {
+        match self {
+            Value::Arr(arr_value) => Some(arr_value),
+            _ => None,
         }
+    }
 
-        if !self.try_match(TokenKind::LeftSquaredBrace) {
-            self.report_error_with_token(self.peek(), "Expected left squared brace!");
-            return None;
+    /// Returns the members when this is a `Value::Obj`.
+    pub fn as_object(&self) -> Option<&HashMap<String, Box<Value>>> {
+        match self {
+            Value::Obj(obj_value) => Some(obj_value),
+            _ => None,
         }
+    }
+}
 
-        let mut result_arr = Vec::<Box<Value>>::new();
-        let mut previous_match_is_element = false;
-        loop {
-            if self.is_at_end() {
-                self.report_error("Unexpected EOF!");
-                return None;
-            }
+/// Encodes a Rust value into the `Value` tree.
+pub trait ToJson {
+    fn to_json(&self) -> Value;
+}
 
-            if self.try_match(TokenKind::RightSquaredBrace) {
-                return Some(Value::Arr(result_arr));
+/// Decodes a Rust value out of a `Value` tree, reporting a structured error
+/// rather than panicking on a shape mismatch.
+pub trait FromJson: Sized {
+    fn from_json(value: &Value) -> Result<Self, DecodeError>;
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::Bool(bool_value) => Ok(*bool_value),
+            _ => Err(DecodeError::type_mismatch("bool")),
+        }
+    }
+}
+
+macro_rules! impl_json_for_signed {
+    ($($t:ty),*) => {$(
+        impl ToJson for $t {
+            fn to_json(&self) -> Value {
+                Value::Num(Number::Int(*self as i64))
             }
+        }
 
-            let next_token = self.peek();
-            if previous_match_is_element {
-                if !self.try_match(TokenKind::Comma) {
-                    self.report_error_with_token(
-                        next_token,
-                        "JSON array elements must be separated by a comma!",
-                    );
-                    return None;
+        impl FromJson for $t {
+            fn from_json(value: &Value) -> Result<Self, DecodeError> {
+                match value {
+                    Value::Num(number) => number
+                        .as_i64()
+                        .and_then(|int_value| <$t>::try_from(int_value).ok())
+                        .ok_or_else(|| DecodeError::type_mismatch(stringify!($t))),
+                    _ => Err(DecodeError::type_mismatch(stringify!($t))),
                 }
             }
+        }
+    )*};
+}
 
-            let maybe_element = self.parse_value();
-            if maybe_element.is_none() {
-                self.report_error_with_token(next_token, "Failed to parse JSON object element!");
-                return None;
+macro_rules! impl_json_for_unsigned {
+    ($($t:ty),*) => {$(
+        impl ToJson for $t {
+            fn to_json(&self) -> Value {
+                Value::Num(Number::Uint(*self as u64))
             }
-
-            let element = maybe_element.unwrap();
-            result_arr.push(Box::new(element));
-            previous_match_is_element = true;
         }
-    }
 
-    fn try_match(&mut self, expected: TokenKind) -> bool {
-        if self.is_at_end() || self.peek().kind != expected {
-            return false;
+        impl FromJson for $t {
+            fn from_json(value: &Value) -> Result<Self, DecodeError> {
+                match value {
+                    Value::Num(number) => number
+                        .as_u64()
+                        .and_then(|uint_value| <$t>::try_from(uint_value).ok())
+                        .ok_or_else(|| DecodeError::type_mismatch(stringify!($t))),
+                    _ => Err(DecodeError::type_mismatch(stringify!($t))),
+                }
+            }
         }
+    )*};
+}
 
-        self.advance();
-        return true;
-    }
+impl_json_for_signed!(i8, i16, i32, i64);
+impl_json_for_unsigned!(u8, u16, u32, u64);
 
-    fn advance(&mut self) -> &'a Token {
-        let result = self.peek();
-        self.current += 1;
-
-        result
+impl ToJson for f64 {
+    fn to_json(&self) -> Value {
+        Value::Num(Number::Float(*self))
     }
+}
 
-    fn peek(&self) -> &'a Token {
-        &self.tokens[self.current]
+impl FromJson for f64 {
+    fn from_json(value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::Num(number) => Ok(number.as_f64()),
+            _ => Err(DecodeError::type_mismatch("f64")),
+        }
     }
+}
 
-    fn is_at_end(&self) -> bool {
-        return self.current >= self.tokens.len();
+impl ToJson for String {
+    fn to_json(&self) -> Value {
+        Value::Str(self.clone())
     }
+}
 
-    fn report_error_with_token(&mut self, token: &Token, message: &str) {
-        self.errors
-            .push(format!("[Line {}] Error: {}", token.line, message));
+impl FromJson for String {
+    fn from_json(value: &Value) -> Result<Self, DecodeError> {
+        value
+            .as_str()
+            .map(|str_value| str_value.to_string())
+            .ok_or_else(|| DecodeError::type_mismatch("String"))
     }
+}
 
-    fn report_error(&mut self, message: &str) {
-        self.errors.push(format!("Error: {}", message));
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Value {
+        match self {
+            Some(inner) => inner.to_json(),
+            None => Value::Null,
+        }
     }
 }
 
-pub fn deserialize_value(buffer: &str) -> Option<Value> {
-    let mut scanner = Scanner::new(buffer);
-    scanner.scan_tokens();
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::Null => Ok(None),
+            _ => Ok(Some(T::from_json(value)?)),
+        }
+    }
+}
 
-    // println!("Scanner tokens = {:?}", scanner.tokens);
-    // println!("Scanner errors = {:?}", scanner.errors);
-    if !scanner.errors.is_empty() {
-        println!("Scanner failed with errors!");
-        return None;
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Value {
+        Value::Arr(self.iter().map(|inner| Box::new(inner.to_json())).collect())
     }
+}
 
-    let mut parser = Parser::new(&scanner.tokens);
-    let maybe_value = parser.parse_tokens();
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Result<Self, DecodeError> {
+        match value.as_array() {
+            Some(arr_value) => arr_value
+                .iter()
+                .map(|element| T::from_json(element))
+                .collect(),
+            None => Err(DecodeError::type_mismatch("array")),
+        }
+    }
+}
 
-    // println!("Parser result = {:?}", maybe_value);
-    // println!("Parser errors = {:?}", parser.errors);
-    if !parser.errors.is_empty() {
-        println!("Parser failed with errors!");
-        return None;
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    fn to_json(&self) -> Value {
+        Value::Obj(
+            self.iter()
+                .map(|(key, inner)| (key.clone(), Box::new(inner.to_json())))
+                .collect(),
+        )
     }
+}
 
-    maybe_value
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &Value) -> Result<Self, DecodeError> {
+        match value.as_object() {
+            Some(obj_value) => obj_value
+                .iter()
+                .map(|(key, element)| Ok((key.clone(), T::from_json(element)?)))
+                .collect(),
+            None => Err(DecodeError::type_mismatch("object")),
+        }
+    }
 }
@@ -16,7 +16,7 @@ fn test_serialization() {
                 Box::new(lib::Value::Arr(vec![
                     Box::new(lib::Value::Str(String::from("abacaba"))),
                     Box::new(lib::Value::Bool(false)),
-                    Box::new(lib::Value::Num(42.0)),
+                    Box::new(lib::Value::Num(lib::Number::Int(42))),
                 ])),
             );
 
@@ -40,15 +40,18 @@ fn test_deserialization() {
 
     let maybe_json_value = lib::deserialize_value(json_buffer);
     match maybe_json_value {
-        Some(json_value) => {
+        Ok(json_value) => {
             println!(
                 "Parsed JSON value (roundtrip) = {}",
                 lib::serialize_value(&json_value)
             );
         }
 
-        None => {
+        Err(errors) => {
             println!("Failed to parse JSON value!");
+            for error in errors {
+                println!("{}", error);
+            }
         }
     }
 }
@@ -79,7 +82,7 @@ fn test_deserialization_external() {
 
     for _ in 0..1000 {
         let maybe_json_value = lib::deserialize_value(&file_data);
-        if maybe_json_value.is_none() {
+        if maybe_json_value.is_err() {
             println!("Failed to deserialize the file as JSON!");
             return;
         }